@@ -1,5 +1,9 @@
-use anyhow::Ok;
-use strum::IntoEnumIterator;
+use std::{collections::HashMap, fs, path::PathBuf, process::Command};
+
+use anyhow::{anyhow, Ok};
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
 
 use crate::{
     commands::CARGO_NIGHTLY_MSG,
@@ -7,54 +11,280 @@ use crate::{
     utils::{
         cargo::ensure_cargo_crate_is_installed, process::run_process,
         rustup::is_current_toolchain_nightly,
+        workspace::{get_workspace_members, WorkspaceMemberType},
     },
 };
 
-#[tracel_xtask_macros::declare_command_args(None, DependenciesSubCommand)]
-pub struct DependenciesCmdArgs {}
+#[derive(Args, Clone)]
+pub struct DependenciesCmdArgs {
+    /// Comma-separated list of excluded crates.
+    #[arg(
+        short = 'x',
+        long,
+        value_name = "CRATE,CRATE,...",
+        value_delimiter = ',',
+        required = false
+    )]
+    pub exclude: Vec<String>,
+    /// Comma-separated list of crates to include exclusively.
+    #[arg(
+        short = 'n',
+        long,
+        value_name = "CRATE,CRATE,...",
+        value_delimiter = ',',
+        required = false
+    )]
+    pub only: Vec<String>,
+    /// Write the consolidated unused dependencies report to this path.
+    #[arg(long, value_name = "PATH", required = false)]
+    pub report: Option<PathBuf>,
+    /// Path to a cargo-deny config file (defaults to deny.toml at the workspace root).
+    #[arg(long, value_name = "PATH", required = false)]
+    pub config: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: DependenciesSubCommand,
+}
+
+#[derive(EnumString, EnumIter, Display, Clone, PartialEq, Subcommand)]
+#[strum(serialize_all = "lowercase")]
+pub enum DependenciesSubCommand {
+    /// Run all cargo-deny checks: advisories, bans, licenses and sources.
+    Deny,
+    /// Check for security advisories against dependencies.
+    Advisories,
+    /// Check for banned or duplicate dependencies.
+    Bans,
+    /// Check license policy compliance.
+    Licenses,
+    /// Check that dependencies come from allowed sources.
+    Sources,
+    /// Find unused dependencies.
+    Unused,
+    /// Run all the checks.
+    All,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct UnusedDeps {
+    #[serde(default)]
+    normal: Vec<String>,
+    #[serde(default)]
+    development: Vec<String>,
+    #[serde(default)]
+    build: Vec<String>,
+}
+
+impl UnusedDeps {
+    fn is_empty(&self) -> bool {
+        self.normal.is_empty() && self.development.is_empty() && self.build.is_empty()
+    }
+
+    /// Merge another scan's findings for the same package into this one, keeping the union
+    /// of unused dependencies instead of letting a later scan overwrite an earlier one.
+    fn merge(&mut self, other: UnusedDeps) {
+        for dep in other.normal {
+            if !self.normal.contains(&dep) {
+                self.normal.push(dep);
+            }
+        }
+        for dep in other.development {
+            if !self.development.contains(&dep) {
+                self.development.push(dep);
+            }
+        }
+        for dep in other.build {
+            if !self.build.contains(&dep) {
+                self.build.push(dep);
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct UdepsOutput {
+    #[serde(default)]
+    unused_deps: HashMap<String, UnusedDeps>,
+}
 
 pub fn handle_command(args: DependenciesCmdArgs) -> anyhow::Result<()> {
-    match args.get_command() {
-        DependenciesSubCommand::Deny => run_cargo_deny(),
-        DependenciesSubCommand::Unused => run_cargo_udeps(),
-        DependenciesSubCommand::All => DependenciesSubCommand::iter()
-            .filter(|c| *c != DependenciesSubCommand::All)
-            .try_for_each(|c| handle_command(DependenciesCmdArgs { command: Some(c) })),
+    match args.command {
+        DependenciesSubCommand::Deny => run_cargo_deny_all(&args.config),
+        DependenciesSubCommand::Advisories => run_cargo_deny(Some("advisories"), &args.config),
+        DependenciesSubCommand::Bans => run_cargo_deny(Some("bans"), &args.config),
+        DependenciesSubCommand::Licenses => run_cargo_deny(Some("licenses"), &args.config),
+        DependenciesSubCommand::Sources => run_cargo_deny(Some("sources"), &args.config),
+        DependenciesSubCommand::Unused => run_cargo_udeps(&args.exclude, &args.only, &args.report),
+        DependenciesSubCommand::All => [DependenciesSubCommand::Deny, DependenciesSubCommand::Unused]
+            .into_iter()
+            .try_for_each(|c| {
+                handle_command(DependenciesCmdArgs {
+                    command: c,
+                    exclude: args.exclude.clone(),
+                    only: args.only.clone(),
+                    report: args.report.clone(),
+                    config: args.config.clone(),
+                })
+            }),
     }
 }
 
-/// Run cargo-deny
-fn run_cargo_deny() -> anyhow::Result<()> {
+/// Run every cargo-deny check kind unconditionally, so a failure in one kind doesn't
+/// prevent the others from reporting, and aggregate the failing kinds into one error.
+fn run_cargo_deny_all(config: &Option<PathBuf>) -> anyhow::Result<()> {
+    let failed_kinds: Vec<&str> = ["advisories", "bans", "licenses", "sources"]
+        .into_iter()
+        .filter(|kind| run_cargo_deny(Some(kind), config).is_err())
+        .collect();
+    if failed_kinds.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "cargo-deny checks failed for: {}",
+            failed_kinds.join(", ")
+        ))
+    }
+}
+
+/// Run a single `cargo deny check <kind>`, or the umbrella check when `kind` is `None`.
+fn run_cargo_deny(kind: Option<&str>, config: &Option<PathBuf>) -> anyhow::Result<()> {
     ensure_cargo_crate_is_installed("cargo-deny", None, None, false)?;
-    // Run cargo deny
-    group!("Cargo: run deny checks");
-    run_process(
-        "cargo",
-        &vec!["deny", "check"],
-        None,
-        None,
-        "Some dependencies don't meet the requirements!",
-    )?;
+    let mut cargo_args = vec!["deny"];
+    if let Some(path) = config {
+        cargo_args.push("--config");
+        cargo_args.push(path.to_str().ok_or_else(|| anyhow!("Invalid config path"))?);
+    }
+    cargo_args.push("check");
+    if let Some(kind) = kind {
+        cargo_args.push(kind);
+    }
+    group!(
+        "Cargo: run deny checks{}",
+        kind.map(|k| format!(" ({k})")).unwrap_or_default()
+    );
+    run_process("cargo", &cargo_args, None, None, deny_error_message(kind))?;
     endgroup!();
     Ok(())
 }
 
-/// Run cargo-udeps
-fn run_cargo_udeps() -> anyhow::Result<()> {
+/// Per-kind error message so CI logs point straight at the failing policy.
+fn deny_error_message(kind: Option<&str>) -> &'static str {
+    match kind {
+        Some("advisories") => "Security advisory found for a dependency!",
+        Some("bans") => "Banned or duplicate dependency found!",
+        Some("licenses") => "License policy violation!",
+        Some("sources") => "Dependency fetched from a disallowed source!",
+        _ => "Some dependencies don't meet the requirements!",
+    }
+}
+
+/// Run cargo-udeps for every included workspace member and aggregate the results into a
+/// single per-crate, per-dependency-kind report.
+fn run_cargo_udeps(
+    excluded: &Vec<String>,
+    only: &Vec<String>,
+    report: &Option<PathBuf>,
+) -> anyhow::Result<()> {
     if is_current_toolchain_nightly() {
         ensure_cargo_crate_is_installed("cargo-udeps", None, None, false)?;
-        // Run cargo udeps
+
+        let mut findings: HashMap<String, UnusedDeps> = HashMap::new();
+        let mut failed_members: Vec<String> = Vec::new();
         group!("Cargo: run unused dependencies checks");
-        run_process(
-            "cargo",
-            &vec!["udeps"],
-            None,
-            None,
-            "Unused dependencies found!",
-        )?;
+        for member in get_workspace_members(WorkspaceMemberType::Crate) {
+            if excluded.contains(&member.name) || (!only.is_empty() && !only.contains(&member.name))
+            {
+                info!("Skip '{}' because it has been excluded!", &member.name);
+                continue;
+            }
+            info!(
+                "Command line: cargo udeps -p {} --output json",
+                &member.name
+            );
+            let output = Command::new("cargo")
+                .args(["udeps", "-p", &member.name, "--output", "json"])
+                .output()
+                .map_err(|e| anyhow!("Failed to execute cargo udeps for {}: {}", &member.name, e))?;
+            // cargo udeps exits non-zero when it finds unused dependencies, so the JSON is
+            // parsed first and a non-zero exit only counts as a failure when it reports none.
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            match serde_json::from_str::<UdepsOutput>(&stdout) {
+                std::result::Result::Ok(parsed) => {
+                    let mut found = false;
+                    for (package, deps) in parsed.unused_deps {
+                        if deps.is_empty() {
+                            continue;
+                        }
+                        found = true;
+                        findings.entry(package).or_default().merge(deps);
+                    }
+                    if !found && !output.status.success() {
+                        error!(
+                            "cargo udeps failed for '{}':\n{}",
+                            &member.name,
+                            String::from_utf8_lossy(&output.stderr)
+                        );
+                        failed_members.push(member.name.clone());
+                    }
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to parse cargo udeps output for '{}': {}",
+                        &member.name, e
+                    );
+                    failed_members.push(member.name.clone());
+                }
+            }
+        }
         endgroup!();
+
+        print_unused_deps_report(&findings);
+        if let Some(path) = report {
+            write_unused_deps_report(&findings, path)?;
+        }
+
+        if !failed_members.is_empty() {
+            return Err(anyhow!(
+                "cargo udeps failed for: {}",
+                failed_members.join(", ")
+            ));
+        }
+
+        if !findings.is_empty() {
+            return Err(anyhow!("Unused dependencies found!"));
+        }
     } else {
         error!("{}", CARGO_NIGHTLY_MSG);
     }
     Ok(())
 }
+
+fn print_unused_deps_report(findings: &HashMap<String, UnusedDeps>) {
+    if findings.is_empty() {
+        info!("No unused dependencies found.");
+        return;
+    }
+    info!("Unused dependencies report:");
+    for (crate_name, deps) in findings {
+        info!("  {}:", crate_name);
+        print_unused_deps_kind("normal", &deps.normal);
+        print_unused_deps_kind("development", &deps.development);
+        print_unused_deps_kind("build", &deps.build);
+    }
+}
+
+fn print_unused_deps_kind(kind: &str, deps: &[String]) {
+    if !deps.is_empty() {
+        info!("    {}: {}", kind, deps.join(", "));
+    }
+}
+
+fn write_unused_deps_report(
+    findings: &HashMap<String, UnusedDeps>,
+    path: &PathBuf,
+) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(findings)
+        .map_err(|e| anyhow!("Failed to serialize unused dependencies report: {}", e))?;
+    fs::write(path, json)
+        .map_err(|e| anyhow!("Failed to write unused dependencies report to {:?}: {}", path, e))?;
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use std::process::Command;
+use std::{
+    collections::VecDeque,
+    process::{Command, Output},
+    sync::Mutex,
+    thread,
+};
 
 use anyhow::{anyhow, Ok, Result};
 use clap::{Args, Subcommand};
@@ -15,6 +20,11 @@ use crate::{
 
 use super::Target;
 
+/// Default worker count for `-j/--jobs`: one per available core.
+fn default_jobs() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
 #[derive(Args, Clone)]
 pub struct CheckCmdArgs {
     /// Target to check for.
@@ -38,6 +48,12 @@ pub struct CheckCmdArgs {
         required = false
     )]
     pub only: Vec<String>,
+    /// Number of crates to process concurrently for format/lint.
+    #[arg(short, long, value_name = "N", default_value_t = default_jobs())]
+    pub jobs: usize,
+    /// Verify only, without applying autofixes. Fails on drift instead of mutating the tree.
+    #[arg(long, default_value_t = false)]
+    pub check_only: bool,
     #[command(subcommand)]
     pub command: CheckCommand,
 }
@@ -59,14 +75,32 @@ pub enum CheckCommand {
 
 pub fn handle_command(args: CheckCmdArgs, answer: Option<bool>) -> anyhow::Result<()> {
     match args.command {
-        CheckCommand::Audit => run_audit(&args.target, answer),
-        CheckCommand::Format => run_format(&args.target, &args.exclude, &args.only, answer),
-        CheckCommand::Lint => run_lint(&args.target, &args.exclude, &args.only, answer),
-        CheckCommand::Typos => run_typos(&args.target, answer),
+        CheckCommand::Audit => run_audit(&args.target, args.check_only, answer),
+        CheckCommand::Format => run_format(
+            &args.target,
+            &args.exclude,
+            &args.only,
+            args.jobs,
+            args.check_only,
+            answer,
+        ),
+        CheckCommand::Lint => run_lint(
+            &args.target,
+            &args.exclude,
+            &args.only,
+            args.jobs,
+            args.check_only,
+            answer,
+        ),
+        CheckCommand::Typos => run_typos(&args.target, args.check_only, answer),
         CheckCommand::All => {
-            let answer = ask_once(
-                "This will run all the checks with autofix on all members of the workspace.",
-            );
+            let answer = if args.check_only {
+                true
+            } else {
+                ask_once(
+                    "This will run all the checks with autofix on all members of the workspace.",
+                )
+            };
             CheckCommand::iter()
                 .filter(|c| *c != CheckCommand::All)
                 .try_for_each(|c| {
@@ -76,6 +110,8 @@ pub fn handle_command(args: CheckCmdArgs, answer: Option<bool>) -> anyhow::Resul
                             target: args.target.clone(),
                             exclude: args.exclude.clone(),
                             only: args.only.clone(),
+                            jobs: args.jobs,
+                            check_only: args.check_only,
                         },
                         Some(answer),
                     )
@@ -84,35 +120,99 @@ pub fn handle_command(args: CheckCmdArgs, answer: Option<bool>) -> anyhow::Resul
     }
 }
 
-pub(crate) fn run_audit(target: &Target, mut answer: Option<bool>) -> anyhow::Result<()> {
+/// Run `run_one` for each name across a bounded worker pool, buffering each job's captured
+/// output so concurrent runs still print one crate at a time, and deferring failures until
+/// every job has run instead of aborting on the first one.
+fn run_parallel<F>(label: &str, names: Vec<String>, jobs: usize, run_one: F) -> anyhow::Result<()>
+where
+    F: Fn(&str) -> anyhow::Result<Output> + Send + Sync,
+{
+    let queue = Mutex::new(VecDeque::from(names));
+    let print_lock = Mutex::new(());
+    let failures = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                let name = match queue.lock().unwrap().pop_front() {
+                    Some(name) => name,
+                    None => break,
+                };
+                let result = run_one(&name);
+                let _guard = print_lock.lock().unwrap();
+                group!("{}: {}", label, name);
+                match result {
+                    std::result::Result::Ok(output) => {
+                        print!("{}", String::from_utf8_lossy(&output.stdout));
+                        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+                        if !output.status.success() {
+                            failures.lock().unwrap().push(name);
+                        }
+                    }
+                    Err(e) => {
+                        error!("{}", e);
+                        failures.lock().unwrap().push(name);
+                    }
+                }
+                endgroup!();
+            });
+        }
+    });
+
+    let failures = failures.into_inner().unwrap();
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow!("{} failed for: {}", label, failures.join(", ")))
+    }
+}
+
+pub(crate) fn run_audit(
+    target: &Target,
+    check_only: bool,
+    mut answer: Option<bool>,
+) -> anyhow::Result<()> {
     match target {
         Target::Crates | Target::Examples => {
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once(
                     "This will run the audit check with autofix mode enabled.",
                 ));
             };
             if answer.unwrap() {
-                ensure_cargo_crate_is_installed("cargo-audit", Some("fix"), None, false)?;
+                let install_feature = if check_only { None } else { Some("fix") };
+                ensure_cargo_crate_is_installed("cargo-audit", install_feature, None, false)?;
                 group!("Audit: Crates and Examples");
-                info!("Command line: cargo audit -q --color always fix");
+                let mut cargo_args = vec!["audit", "-q", "--color", "always"];
+                if !check_only {
+                    cargo_args.push("fix");
+                }
+                info!("Command line: cargo {}", cargo_args.join(" "));
                 let status = Command::new("cargo")
-                    .args(["audit", "-q", "--color", "always", "fix"])
+                    .args(&cargo_args)
                     .status()
                     .map_err(|e| anyhow!("Failed to execute cargo audit: {}", e))?;
                 if !status.success() {
-                    return Err(anyhow!("Audit check execution failed"));
+                    return Err(anyhow!(if check_only {
+                        "Audit check found vulnerabilities"
+                    } else {
+                        "Audit check execution failed"
+                    }));
                 }
                 endgroup!();
             }
         }
         Target::All => {
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once("This will run audit checks on all targets."));
             };
             Target::iter()
                 .filter(|p| *p != Target::All && *p != Target::Examples)
-                .try_for_each(|p| run_audit(&p, answer))?;
+                .try_for_each(|p| run_audit(&p, check_only, answer))?;
         }
     }
     Ok(())
@@ -122,6 +222,8 @@ fn run_format(
     target: &Target,
     excluded: &Vec<String>,
     only: &Vec<String>,
+    jobs: usize,
+    check_only: bool,
     mut answer: Option<bool>,
 ) -> Result<()> {
     match target {
@@ -132,7 +234,9 @@ fn run_format(
                 _ => unreachable!(),
             };
 
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once(&format!(
                     "This will run format checks on all {} of the workspace.",
                     if *target == Target::Crates {
@@ -144,31 +248,36 @@ fn run_format(
             }
 
             if answer.unwrap() {
-                for member in members {
-                    group!("Format: {}", member.name);
-                    if excluded.contains(&member.name)
-                        || (!only.is_empty() && !only.contains(&member.name))
-                    {
-                        info!("Skip '{}' because it has been excluded!", &member.name);
-                        continue;
+                let names: Vec<String> = members
+                    .into_iter()
+                    .map(|m| m.name)
+                    .filter(|name| {
+                        if excluded.contains(name) || (!only.is_empty() && !only.contains(name)) {
+                            info!("Skip '{}' because it has been excluded!", name);
+                            false
+                        } else {
+                            true
+                        }
+                    })
+                    .collect();
+                run_parallel("Format", names, jobs, |name| {
+                    let mut fmt_args = vec!["fmt", "-p", name, "--"];
+                    if check_only {
+                        fmt_args.push("--check");
                     }
-                    info!("Command line: cargo fmt -p {} -- --color=always", &member.name);
-                    let status = Command::new("cargo")
-                        .args(["fmt", "-p", &member.name, "--", "--color=always"])
-                        .status()
-                        .map_err(|e| anyhow!("Failed to execute cargo fmt: {}", e))?;
-                    if !status.success() {
-                        return Err(anyhow!(
-                            "Format check execution failed for {}",
-                            &member.name
-                        ));
-                    }
-                    endgroup!();
-                }
+                    fmt_args.push("--color=always");
+                    info!("Command line: cargo {}", fmt_args.join(" "));
+                    Command::new("cargo")
+                        .args(&fmt_args)
+                        .output()
+                        .map_err(|e| anyhow!("Failed to execute cargo fmt: {}", e))
+                })?;
             }
         }
         Target::All => {
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once(
                     "This will run format check on all members of the workspace.",
                 ));
@@ -176,7 +285,7 @@ fn run_format(
             if answer.unwrap() {
                 Target::iter()
                     .filter(|t| *t != Target::All)
-                    .try_for_each(|t| run_format(&t, excluded, only, answer))?;
+                    .try_for_each(|t| run_format(&t, excluded, only, jobs, check_only, answer))?;
             }
         }
     }
@@ -187,6 +296,8 @@ fn run_lint(
     target: &Target,
     excluded: &Vec<String>,
     only: &Vec<String>,
+    jobs: usize,
+    check_only: bool,
     mut answer: Option<bool>,
 ) -> anyhow::Result<()> {
     match target {
@@ -197,7 +308,9 @@ fn run_lint(
                 _ => unreachable!(),
             };
 
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once(&format!(
                     "This will run lint fix on all {} of the workspace.",
                     if *target == Target::Crates {
@@ -209,43 +322,38 @@ fn run_lint(
             }
 
             if answer.unwrap() {
-                for member in members {
-                    group!("Lint: {}", member.name);
-                    if excluded.contains(&member.name)
-                        || (!only.is_empty() && !only.contains(&member.name))
-                    {
-                        info!("Skip '{}' because it has been excluded!", &member.name);
-                        continue;
-                    }
-                    info!(
-                        "Command line: cargo clippy --no-deps --fix --allow-dirty --allow-staged --color=always -p {} -- --deny warnings",
-                        &member.name
-                    );
-                    let status = Command::new("cargo")
-                        .args([
-                            "clippy",
-                            "--no-deps",
-                            "--fix",
-                            "--allow-dirty",
-                            "--allow-staged",
-                            "--color=always",
-                            "-p",
-                            &member.name,
-                            "--",
-                            "--deny",
-                            "warnings"
-                        ])
-                        .status()
-                        .map_err(|e| anyhow!("Failed to execute cargo clippy: {}", e))?;
-                    if !status.success() {
-                        return Err(anyhow!("Lint fix execution failed for {}", &member.name));
+                let names: Vec<String> = members
+                    .into_iter()
+                    .map(|m| m.name)
+                    .filter(|name| {
+                        if excluded.contains(name) || (!only.is_empty() && !only.contains(name)) {
+                            info!("Skip '{}' because it has been excluded!", name);
+                            false
+                        } else {
+                            true
+                        }
+                    })
+                    .collect();
+                run_parallel("Lint", names, jobs, |name| {
+                    let mut clippy_args = vec!["clippy", "--no-deps"];
+                    if check_only {
+                        clippy_args.push("--color=always");
+                    } else {
+                        clippy_args.extend(["--fix", "--allow-dirty", "--allow-staged", "--color=always"]);
                     }
-                    endgroup!();
-                }
+                    clippy_args.extend(["-p", name, "--", "--deny", "warnings"]);
+                    info!("Command line: cargo {}", clippy_args.join(" "));
+                    Command::new("cargo")
+                        .args(&clippy_args)
+                        .output()
+                        .map_err(|e| anyhow!("Failed to execute cargo clippy: {}", e))
+                })?;
             }
         }
         Target::All => {
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once(
                     "This will run lint fix on all members of the workspace.",
                 ));
@@ -253,17 +361,23 @@ fn run_lint(
             if answer.unwrap() {
                 Target::iter()
                     .filter(|t| *t != Target::All)
-                    .try_for_each(|t| run_lint(&t, excluded, only, answer))?;
+                    .try_for_each(|t| run_lint(&t, excluded, only, jobs, check_only, answer))?;
             }
         }
     }
     Ok(())
 }
 
-pub(crate) fn run_typos(target: &Target, mut answer: Option<bool>) -> anyhow::Result<()> {
+pub(crate) fn run_typos(
+    target: &Target,
+    check_only: bool,
+    mut answer: Option<bool>,
+) -> anyhow::Result<()> {
     match target {
         Target::Crates | Target::Examples => {
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once(
                     "This will look for typos in the source code check and auto-fix them.",
                 ));
@@ -271,24 +385,34 @@ pub(crate) fn run_typos(target: &Target, mut answer: Option<bool>) -> anyhow::Re
             if answer.unwrap() {
                 ensure_cargo_crate_is_installed("typos-cli", None, Some(TYPOS_VERSION), false)?;
                 group!("Typos: Crates and Examples");
-                info!("Command line: typos --write-changes");
+                let mut typos_args = vec![];
+                if !check_only {
+                    typos_args.push("--write-changes");
+                }
+                info!("Command line: typos {}", typos_args.join(" "));
                 let status = Command::new("typos")
-                    .args(["--write-changes"])
+                    .args(&typos_args)
                     .status()
                     .map_err(|e| anyhow!("Failed to execute typos: {}", e))?;
                 if !status.success() {
-                    return Err(anyhow!("Some typos have been found and cannot be fixed."));
+                    return Err(anyhow!(if check_only {
+                        "Typos were found in the source code."
+                    } else {
+                        "Some typos have been found and cannot be fixed."
+                    }));
                 }
                 endgroup!();
             }
         }
         Target::All => {
-            if answer.is_none() {
+            if check_only {
+                answer = Some(true);
+            } else if answer.is_none() {
                 answer = Some(ask_once("This will look for typos on all targets."));
             };
             Target::iter()
                 .filter(|p| *p != Target::All && *p != Target::Examples)
-                .try_for_each(|p| run_typos(&p, answer))?;
+                .try_for_each(|p| run_typos(&p, check_only, answer))?;
         }
     }
     Ok(())
@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Ok};
+use clap::{Args, Subcommand};
+use strum::{Display, EnumIter, EnumString};
+
+use crate::{
+    endgroup, group,
+    utils::{cargo::ensure_cargo_crate_is_installed, process::run_process},
+};
+
+use super::Profile;
+
+#[derive(Args, Clone)]
+pub struct CoverageCmdArgs {
+    /// Build profile to use when running the instrumented tests.
+    #[arg(short, long, value_enum, default_value_t = Profile::Debug)]
+    pub profile: Profile,
+    #[command(subcommand)]
+    pub command: CoverageSubCommand,
+}
+
+#[derive(EnumString, EnumIter, Display, Clone, PartialEq, Subcommand)]
+#[strum(serialize_all = "lowercase")]
+pub enum CoverageSubCommand {
+    /// Generate an lcov report at lcov.info.
+    Lcov,
+    /// Generate an HTML coverage report.
+    Html,
+    /// Generate a JSON coverage report.
+    Json {
+        /// Path the JSON report is written to.
+        #[arg(short, long, default_value = "coverage.json")]
+        output_path: String,
+    },
+}
+
+pub fn handle_command(args: CoverageCmdArgs) -> anyhow::Result<()> {
+    ensure_cargo_crate_is_installed("cargo-llvm-cov", None, None, false)?;
+    match &args.command {
+        CoverageSubCommand::Lcov => {
+            run_llvm_cov(&["llvm-cov", "--lcov", "--output-path", "lcov.info"], &args.profile)
+        }
+        CoverageSubCommand::Html => run_llvm_cov(&["llvm-cov", "--html"], &args.profile),
+        CoverageSubCommand::Json { output_path } => run_llvm_cov(
+            &["llvm-cov", "--json", "--output-path", output_path],
+            &args.profile,
+        ),
+    }
+}
+
+/// Run cargo-llvm-cov with the given report flags, letting it drive its own
+/// instrument/run/merge/export pipeline under the hood.
+fn run_llvm_cov(cargo_args: &[&str], profile: &Profile) -> anyhow::Result<()> {
+    let mut cargo_args = cargo_args.to_vec();
+    match profile {
+        Profile::Release => cargo_args.push("--release"),
+        Profile::Debug => {}
+        Profile::All => {
+            return Err(anyhow!(
+                "Coverage can only be generated for a single profile; pass --profile debug or --profile release."
+            ))
+        }
+    }
+    group!("Coverage: run cargo-llvm-cov");
+    run_process(
+        "cargo",
+        &cargo_args,
+        None,
+        None,
+        "Failed to generate the coverage report!",
+    )?;
+    endgroup!();
+    Ok(())
+}